@@ -0,0 +1,149 @@
+// Copyright 2018-2021 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Free-standing functions that contracts call to interact with their
+//! execution environment, mirroring the layout of the `Environment` trait
+//! in [`crate::types`].
+
+use crate::{
+    backend::{
+        EnvBackend,
+        TypedEnvBackend,
+    },
+    engine::{
+        EnvInstance,
+        OnInstance,
+    },
+    types::Weight,
+    Environment,
+};
+
+/// Returns the chain identifier of the runtime executing the contract.
+///
+/// This is a compile-time property of `T` (see [`Environment::CHAIN_ID`]),
+/// so unlike most other accessors in this module it never touches the host.
+/// This function is not a `const fn`; in `const` contexts the same value is
+/// available as `T::CHAIN_ID`.
+///
+/// # Note
+///
+/// The main use case is signed-message verification: bind the signed
+/// payload to `chain_id::<T>()` so that a signature valid on one chain
+/// (e.g. a test chain) cannot be replayed on another (e.g. production).
+pub fn chain_id<T>() -> u64
+where
+    T: Environment,
+{
+    T::CHAIN_ID
+}
+
+/// Writes `value` to transient storage under `key`.
+///
+/// # Panics
+///
+/// Panics if `T::SUPPORTS_TRANSIENT_STORAGE` is `false`: the target
+/// environment has no transient storage for this call to dispatch to.
+///
+/// Keyed the same way as [`crate::set_contract_storage`], but the written
+/// value is wiped at the end of the current transaction (and rolled back
+/// immediately on revert) instead of persisting, making this well suited to
+/// reentrancy guards and other intra-call scratch state.
+///
+/// # Note
+///
+/// The off-chain [`EnvInstance`] keeps transient entries in a map that is
+/// cleared only by its `clear_transient_storage_map` method; it is not
+/// cleared automatically between simulated transactions, and nothing is
+/// rolled back on revert.
+pub fn set_transient_storage<T, V>(key: &[u8], value: &V)
+where
+    T: Environment,
+    V: scale::Encode,
+{
+    assert!(
+        T::SUPPORTS_TRANSIENT_STORAGE,
+        "the target environment does not support transient storage"
+    );
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        EnvBackend::set_transient_storage::<T, V>(instance, key, value)
+    })
+}
+
+/// Returns the value stored in transient storage under `key`, if any.
+///
+/// See [`set_transient_storage`] for the lifetime of transient entries and
+/// the `T::SUPPORTS_TRANSIENT_STORAGE` panic condition.
+pub fn get_transient_storage<T, R>(key: &[u8]) -> Option<R>
+where
+    T: Environment,
+    R: scale::Decode,
+{
+    assert!(
+        T::SUPPORTS_TRANSIENT_STORAGE,
+        "the target environment does not support transient storage"
+    );
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        EnvBackend::get_transient_storage::<T, R>(instance, key)
+    })
+}
+
+/// Clears the transient storage entry under `key`, if any.
+///
+/// See [`set_transient_storage`] for the lifetime of transient entries and
+/// the `T::SUPPORTS_TRANSIENT_STORAGE` panic condition.
+pub fn clear_transient_storage<T>(key: &[u8])
+where
+    T: Environment,
+{
+    assert!(
+        T::SUPPORTS_TRANSIENT_STORAGE,
+        "the target environment does not support transient storage"
+    );
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        EnvBackend::clear_transient_storage::<T>(instance, key)
+    })
+}
+
+/// Returns a random value of `T::Randomness`, domain-separated by hashing
+/// `subject` together with the runtime seed.
+///
+/// # Note
+///
+/// `T::Randomness` is pluggable per `Environment` (see
+/// [`Environment::Randomness`]) precisely so that callers needing the block
+/// at which the value became known (e.g. [`RandomnessOutput`]) can opt into
+/// that shape, while environments with a simpler randomness source aren't
+/// forced to carry the extra field.
+///
+/// [`RandomnessOutput`]: crate::types::RandomnessOutput
+pub fn random<T>(subject: &[u8]) -> T::Randomness
+where
+    T: Environment,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        TypedEnvBackend::random::<T>(instance, subject)
+    })
+}
+
+/// Returns the execution weight remaining for the current call.
+///
+/// Returned as a two-dimensional [`Weight`] (`ref_time` plus `proof_size`)
+/// rather than a single `u64`, matching how modern substrate runtimes meter
+/// execution, so contracts doing adaptive batch sizing can query both
+/// dimensions of the remaining budget.
+pub fn weight_left() -> Weight {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        EnvBackend::weight_left(instance)
+    })
+}
@@ -0,0 +1,56 @@
+// Copyright 2018-2021 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The traits implemented by the on-chain and off-chain environment
+//! backends in [`crate::engine`] that [`crate::api`] dispatches to.
+
+use crate::Environment;
+
+/// Environment functions that are generic over the contract's `Environment`.
+pub trait EnvBackend {
+    /// Writes `value` to transient storage under `key`.
+    ///
+    /// Callers are expected to have already checked
+    /// `T::SUPPORTS_TRANSIENT_STORAGE`; implementations are free to assume
+    /// it holds.
+    fn set_transient_storage<T, V>(&mut self, key: &[u8], value: &V)
+    where
+        T: Environment,
+        V: scale::Encode;
+
+    /// Returns the value stored in transient storage under `key`, if any.
+    fn get_transient_storage<T, R>(&mut self, key: &[u8]) -> Option<R>
+    where
+        T: Environment,
+        R: scale::Decode;
+
+    /// Clears the transient storage entry under `key`, if any.
+    fn clear_transient_storage<T>(&mut self, key: &[u8])
+    where
+        T: Environment;
+
+    /// Returns the execution weight remaining for the current call.
+    fn weight_left(&mut self) -> crate::types::Weight;
+}
+
+/// Environment functions that return a value shaped by the contract's
+/// `Environment`, as opposed to a value chosen by the caller (`T` only
+/// appears in the return type, never the parameters).
+pub trait TypedEnvBackend: EnvBackend {
+    /// Returns a random value of `T::Randomness`, domain-separated by hashing
+    /// `subject` together with the runtime seed.
+    fn random<T>(&mut self, subject: &[u8]) -> T::Randomness
+    where
+        T: Environment;
+}
@@ -0,0 +1,187 @@
+// Copyright 2018-2021 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Builders for cross-contract calls and instantiations.
+//!
+//! Both builders are consumed by a `params()` call that produces a plain,
+//! SCALE-encodable parameter struct; it is that struct, not the builder
+//! itself, that gets sent across the host boundary.
+
+use crate::{
+    types::Weight,
+    Environment,
+};
+use ink_prelude::vec::Vec;
+
+/// The parameters of a cross-contract call, as sent to the host.
+///
+/// Built up via [`CallBuilder`] rather than constructed directly.
+#[derive(scale::Encode)]
+pub struct CallParams<E: Environment> {
+    callee: E::AccountId,
+    ref_time_limit: u64,
+    proof_size_limit: u64,
+    /// Caps how much of the callee's storage deposit this call may charge;
+    /// `None` means "no limit", matching [`crate::types::StorageDeposit`].
+    storage_deposit_limit: Option<E::Balance>,
+    transferred_value: E::Balance,
+    input_data: Vec<u8>,
+}
+
+/// Builds up the parameters of a cross-contract call.
+pub struct CallBuilder<E: Environment> {
+    callee: E::AccountId,
+    ref_time_limit: u64,
+    proof_size_limit: u64,
+    storage_deposit_limit: Option<E::Balance>,
+    transferred_value: E::Balance,
+    input_data: Vec<u8>,
+}
+
+impl<E: Environment> CallBuilder<E> {
+    /// Starts building a call to `callee` with no transferred value, no
+    /// weight limit, no storage deposit limit, and no input data.
+    pub fn new(callee: E::AccountId) -> Self {
+        Self {
+            callee,
+            ref_time_limit: 0,
+            proof_size_limit: 0,
+            storage_deposit_limit: None,
+            transferred_value: Default::default(),
+            input_data: Vec::new(),
+        }
+    }
+
+    /// Sets the maximum execution weight the call may consume.
+    pub fn weight_limit(mut self, weight: Weight) -> Self {
+        self.ref_time_limit = weight.ref_time;
+        self.proof_size_limit = weight.proof_size;
+        self
+    }
+
+    /// Caps how much of the callee's storage deposit this call may charge.
+    ///
+    /// Left unset (`None`), the call may charge an unbounded deposit; see
+    /// [`crate::types::StorageDeposit`] for the deposit model this bounds.
+    pub fn storage_deposit_limit(mut self, limit: E::Balance) -> Self {
+        self.storage_deposit_limit = Some(limit);
+        self
+    }
+
+    /// Sets the value transferred to the callee as part of the call.
+    pub fn transferred_value(mut self, value: E::Balance) -> Self {
+        self.transferred_value = value;
+        self
+    }
+
+    /// Sets the SCALE-encoded selector and arguments passed to the callee.
+    pub fn input_data(mut self, input_data: Vec<u8>) -> Self {
+        self.input_data = input_data;
+        self
+    }
+
+    /// Finalizes the builder into the parameters sent to the host.
+    pub fn params(self) -> CallParams<E> {
+        CallParams {
+            callee: self.callee,
+            ref_time_limit: self.ref_time_limit,
+            proof_size_limit: self.proof_size_limit,
+            storage_deposit_limit: self.storage_deposit_limit,
+            transferred_value: self.transferred_value,
+            input_data: self.input_data,
+        }
+    }
+}
+
+/// The parameters of a cross-contract instantiation, as sent to the host.
+///
+/// Built up via [`InstantiateBuilder`] rather than constructed directly.
+#[derive(scale::Encode)]
+pub struct InstantiateParams<E: Environment> {
+    code_hash: E::Hash,
+    ref_time_limit: u64,
+    proof_size_limit: u64,
+    /// Caps how much of the new contract's storage deposit this
+    /// instantiation may charge; `None` means "no limit".
+    storage_deposit_limit: Option<E::Balance>,
+    endowment: E::Balance,
+    input_data: Vec<u8>,
+}
+
+/// Builds up the parameters of a cross-contract instantiation.
+pub struct InstantiateBuilder<E: Environment> {
+    code_hash: E::Hash,
+    ref_time_limit: u64,
+    proof_size_limit: u64,
+    storage_deposit_limit: Option<E::Balance>,
+    endowment: E::Balance,
+    input_data: Vec<u8>,
+}
+
+impl<E: Environment> InstantiateBuilder<E> {
+    /// Starts building an instantiation of `code_hash` with no endowment, no
+    /// weight limit, no storage deposit limit, and no input data.
+    pub fn new(code_hash: E::Hash) -> Self {
+        Self {
+            code_hash,
+            ref_time_limit: 0,
+            proof_size_limit: 0,
+            storage_deposit_limit: None,
+            endowment: Default::default(),
+            input_data: Vec::new(),
+        }
+    }
+
+    /// Sets the maximum execution weight the instantiation may consume.
+    pub fn weight_limit(mut self, weight: Weight) -> Self {
+        self.ref_time_limit = weight.ref_time;
+        self.proof_size_limit = weight.proof_size;
+        self
+    }
+
+    /// Caps how much of the new contract's storage deposit this
+    /// instantiation may charge.
+    ///
+    /// Left unset (`None`), the instantiation may charge an unbounded
+    /// deposit; see [`crate::types::StorageDeposit`] for the deposit model
+    /// this bounds.
+    pub fn storage_deposit_limit(mut self, limit: E::Balance) -> Self {
+        self.storage_deposit_limit = Some(limit);
+        self
+    }
+
+    /// Sets the balance endowed to the new contract on instantiation.
+    pub fn endowment(mut self, endowment: E::Balance) -> Self {
+        self.endowment = endowment;
+        self
+    }
+
+    /// Sets the SCALE-encoded constructor selector and arguments.
+    pub fn input_data(mut self, input_data: Vec<u8>) -> Self {
+        self.input_data = input_data;
+        self
+    }
+
+    /// Finalizes the builder into the parameters sent to the host.
+    pub fn params(self) -> InstantiateParams<E> {
+        InstantiateParams {
+            code_hash: self.code_hash,
+            ref_time_limit: self.ref_time_limit,
+            proof_size_limit: self.proof_size_limit,
+            storage_deposit_limit: self.storage_deposit_limit,
+            endowment: self.endowment,
+            input_data: self.input_data,
+        }
+    }
+}
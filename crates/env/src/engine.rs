@@ -0,0 +1,260 @@
+// Copyright 2018-2021 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Dispatches the functions in [`crate::api`] to either a real on-chain
+//! host (when compiled for the contract's Wasm target) or an emulated
+//! off-chain instance used by unit tests (when the `std` feature is
+//! enabled).
+
+use crate::backend::{
+    EnvBackend,
+    TypedEnvBackend,
+};
+
+/// Provides access to the thread- (or program-) local environment instance
+/// that [`crate::api`] dispatches host calls through.
+pub trait OnInstance: EnvBackend {
+    /// Runs `f` against the current environment instance.
+    fn on_instance<F, R>(f: F) -> R
+    where
+        F: FnOnce(&mut Self) -> R;
+}
+
+#[cfg(not(feature = "std"))]
+mod on_chain {
+    use super::*;
+
+    extern "C" {
+        fn ext_set_transient_storage(
+            key_ptr: *const u8,
+            key_len: u32,
+            value_ptr: *const u8,
+            value_len: u32,
+        );
+        fn ext_get_transient_storage(
+            key_ptr: *const u8,
+            key_len: u32,
+            out_ptr: *mut u8,
+            out_len_ptr: *mut u32,
+        ) -> u32;
+        fn ext_clear_transient_storage(key_ptr: *const u8, key_len: u32);
+        fn ext_random(
+            subject_ptr: *const u8,
+            subject_len: u32,
+            out_ptr: *mut u8,
+            out_len_ptr: *mut u32,
+        );
+        fn ext_weight_left(out_ptr: *mut u8, out_len_ptr: *mut u32);
+    }
+
+    /// The on-chain environment instance: a zero-sized handle to the host
+    /// functions imported above.
+    pub struct EnvInstance;
+
+    impl EnvBackend for EnvInstance {
+        fn set_transient_storage<T, V>(&mut self, key: &[u8], value: &V)
+        where
+            T: crate::Environment,
+            V: scale::Encode,
+        {
+            let encoded = value.encode();
+            unsafe {
+                ext_set_transient_storage(
+                    key.as_ptr(),
+                    key.len() as u32,
+                    encoded.as_ptr(),
+                    encoded.len() as u32,
+                )
+            }
+        }
+
+        fn get_transient_storage<T, R>(&mut self, key: &[u8]) -> Option<R>
+        where
+            T: crate::Environment,
+            R: scale::Decode,
+        {
+            let mut buffer = [0u8; 1024];
+            let mut written = buffer.len() as u32;
+            let found = unsafe {
+                ext_get_transient_storage(
+                    key.as_ptr(),
+                    key.len() as u32,
+                    buffer.as_mut_ptr(),
+                    &mut written as *mut u32,
+                )
+            };
+            if found == 0 {
+                return None
+            }
+            Some(
+                R::decode(&mut &buffer[..written as usize])
+                    .expect("host returned a malformed transient storage value"),
+            )
+        }
+
+        fn clear_transient_storage<T>(&mut self, key: &[u8])
+        where
+            T: crate::Environment,
+        {
+            unsafe { ext_clear_transient_storage(key.as_ptr(), key.len() as u32) }
+        }
+
+        fn weight_left(&mut self) -> crate::types::Weight {
+            let mut buffer = [0u8; 64];
+            let mut written = buffer.len() as u32;
+            unsafe { ext_weight_left(buffer.as_mut_ptr(), &mut written as *mut u32) }
+            scale::Decode::decode(&mut &buffer[..written as usize])
+                .expect("host returned a malformed Weight")
+        }
+    }
+
+    impl TypedEnvBackend for EnvInstance {
+        fn random<T>(&mut self, subject: &[u8]) -> T::Randomness
+        where
+            T: crate::Environment,
+        {
+            let mut buffer = [0u8; 128];
+            let mut written = buffer.len() as u32;
+            unsafe {
+                ext_random(
+                    subject.as_ptr(),
+                    subject.len() as u32,
+                    buffer.as_mut_ptr(),
+                    &mut written as *mut u32,
+                )
+            }
+            <T::Randomness as scale::Decode>::decode(&mut &buffer[..written as usize])
+                .expect("host returned a malformed Randomness value")
+        }
+    }
+
+    impl OnInstance for EnvInstance {
+        fn on_instance<F, R>(f: F) -> R
+        where
+            F: FnOnce(&mut Self) -> R,
+        {
+            f(&mut EnvInstance)
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+mod off_chain {
+    use super::*;
+    use std::{
+        cell::RefCell,
+        collections::HashMap,
+    };
+
+    /// The off-chain environment instance used by contract unit tests.
+    ///
+    /// Emulates the subset of host functions implemented in this module; it
+    /// does not talk to a real runtime.
+    #[derive(Default)]
+    pub struct EnvInstance {
+        transient_storage: HashMap<Vec<u8>, Vec<u8>>,
+    }
+
+    impl EnvInstance {
+        /// Clears the transient-storage map, mirroring how the runtime wipes
+        /// it at the end of each transaction (and on revert).
+        ///
+        /// Off-chain test helpers that simulate moving on to a new
+        /// transaction or block should call this so unit tests observe the
+        /// same clean slate a live transaction would.
+        pub fn clear_transient_storage_map(&mut self) {
+            self.transient_storage.clear();
+        }
+    }
+
+    impl EnvBackend for EnvInstance {
+        fn set_transient_storage<T, V>(&mut self, key: &[u8], value: &V)
+        where
+            T: crate::Environment,
+            V: scale::Encode,
+        {
+            self.transient_storage
+                .insert(key.to_vec(), value.encode());
+        }
+
+        fn get_transient_storage<T, R>(&mut self, key: &[u8]) -> Option<R>
+        where
+            T: crate::Environment,
+            R: scale::Decode,
+        {
+            self.transient_storage.get(key).map(|bytes| {
+                R::decode(&mut &bytes[..])
+                    .expect("corrupted transient storage entry")
+            })
+        }
+
+        fn clear_transient_storage<T>(&mut self, key: &[u8])
+        where
+            T: crate::Environment,
+        {
+            self.transient_storage.remove(key);
+        }
+
+        fn weight_left(&mut self) -> crate::types::Weight {
+            // The off-chain environment has no gas meter; report an
+            // effectively unlimited budget so test code exercising adaptive
+            // batch sizing doesn't spuriously see it run out.
+            crate::types::Weight {
+                ref_time: u64::MAX,
+                proof_size: u64::MAX,
+            }
+        }
+    }
+
+    impl TypedEnvBackend for EnvInstance {
+        fn random<T>(&mut self, subject: &[u8]) -> T::Randomness
+        where
+            T: crate::Environment,
+        {
+            // The off-chain environment has no runtime seed to mix in; fold
+            // `subject` into a fixed-size buffer so that repeated calls with
+            // the same subject are at least deterministic within a test.
+            // Distinct subjects do not always yield distinct values: the
+            // subject is repeated cyclically (so `b"a"` and `b"aa"` fill the
+            // buffer identically) and only the leading bytes that
+            // `T::Randomness` decodes are used. This is not cryptographically
+            // meaningful randomness.
+            let mut buffer = [0u8; 128];
+            for (i, byte) in buffer.iter_mut().enumerate() {
+                *byte = subject.get(i % subject.len().max(1)).copied().unwrap_or(0)
+                    ^ (i as u8);
+            }
+            <T::Randomness as scale::Decode>::decode(&mut &buffer[..])
+                .expect("failed to synthesize an off-chain Randomness value")
+        }
+    }
+
+    impl OnInstance for EnvInstance {
+        fn on_instance<F, R>(f: F) -> R
+        where
+            F: FnOnce(&mut Self) -> R,
+        {
+            thread_local!(
+                static INSTANCE: RefCell<EnvInstance> =
+                    RefCell::new(EnvInstance::default());
+            );
+            INSTANCE.with(|instance| f(&mut instance.borrow_mut()))
+        }
+    }
+}
+
+#[cfg(not(feature = "std"))]
+pub use on_chain::EnvInstance;
+#[cfg(feature = "std")]
+pub use off_chain::EnvInstance;
@@ -30,6 +30,21 @@
 //! Outside the contract and its tests (e.g. in the off-chain environment), where
 //! there is no knowledge of the concrete types, the functionality is restricted to
 //! the trait bounds on the `Environment` trait types.
+//!
+//! # Migrating off the legacy rent mechanism
+//!
+//! [`RentParams`]/[`RentStatus`]/[`Environment::RentFraction`] are gated
+//! behind the `ink-legacy-rent` feature and are superseded by the
+//! storage-deposit model (see [`StorageDeposit`]). This is a breaking gate:
+//! an `Environment` impl that previously set `RentFraction` unconditionally
+//! will fail to compile once the feature is off, since the associated type
+//! no longer exists on the trait. Runtimes that still charge rent must
+//! enable this crate's `ink-legacy-rent` feature;
+//! runtimes on the storage-deposit model should drop their `RentFraction`
+//! impl and any rent-era call sites entirely rather than leaving them
+//! behind an `#[cfg]`. This crate's own manifest is outside this snapshot,
+//! so the feature itself is declared wherever `crates/env`'s `Cargo.toml`
+//! lives, not here.
 
 use super::arithmetic::AtLeast32BitUnsigned;
 use core::{
@@ -43,6 +58,7 @@ use scale::{
 };
 #[cfg(feature = "std")]
 use scale_info::TypeInfo;
+#[cfg(feature = "ink-legacy-rent")]
 use sp_arithmetic::PerThing;
 pub use sp_arithmetic::Perbill;
 
@@ -53,6 +69,48 @@ pub trait Environment {
     /// The value must match the maximum number of supported event topics of the used runtime.
     const MAX_EVENT_TOPICS: usize;
 
+    /// The maximum number of 64KiB Wasm memory pages a contract may allocate.
+    ///
+    /// Mirrors `Schedule::limits.memory_pages`. Build tooling can use this to
+    /// statically reject contracts whose linear memory could exceed what the
+    /// target runtime allows.
+    const MAX_MEMORY_PAGES: u32;
+
+    /// The maximum Wasm stack height, in values, a contract's execution may use.
+    ///
+    /// Mirrors `Schedule::limits.stack_height`.
+    const MAX_STACK_HEIGHT: u32;
+
+    /// The maximum depth of nested cross-contract calls the runtime permits.
+    ///
+    /// Mirrors `Schedule::limits.call_depth`. A contract whose recursive calls
+    /// (e.g. walking a recursive data structure across contract boundaries)
+    /// could exceed this should fail to build rather than trap at runtime.
+    const MAX_CALL_DEPTH: u32;
+
+    /// The chain identifier of the runtime this environment targets.
+    ///
+    /// Mirrors the `chain_id` host function exposed by pallet-revive so that
+    /// contracts can bind signed payloads to a specific chain (EIP-155-style
+    /// replay protection): a signature produced for one `CHAIN_ID` must be
+    /// rejected when replayed on a chain with a different one. Accessible
+    /// from contract code via `ink_env::chain_id()`.
+    const CHAIN_ID: u64;
+
+    /// Whether the runtime backing this environment supports transient
+    /// (per-transaction) storage, i.e. pallet-revive's TLOAD/TSTORE-style
+    /// storage that is cleared at the end of each transaction and rolled
+    /// back on revert.
+    ///
+    /// Transient storage is far cheaper than permanent storage and is well
+    /// suited to reentrancy guards and intra-call scratch state. When this
+    /// is `true`, contracts may use the `ink_env::set_transient_storage`,
+    /// `ink_env::get_transient_storage` and `ink_env::clear_transient_storage`
+    /// host-function bindings, which are keyed the same way as their
+    /// permanent-storage counterparts but are backed by a transaction-scoped
+    /// map instead.
+    const SUPPORTS_TRANSIENT_STORAGE: bool;
+
     /// The address type.
     type AccountId: 'static + scale::Codec + Clone + PartialEq + Eq + Ord;
 
@@ -95,6 +153,15 @@ pub trait Environment {
         + Eq
         + AtLeast32BitUnsigned;
 
+    /// The result of querying on-chain randomness, mirroring
+    /// `pallet_contracts::Config::Randomness`.
+    ///
+    /// Exposed to contract code via `ink_env::random(subject)`, which hashes
+    /// `subject` together with the runtime seed for domain separation before
+    /// returning the random value. See [`RandomnessOutput`] for the shape of
+    /// the value `DefaultEnvironment` and `EthEnvironment` use.
+    type Randomness: 'static + scale::Codec + Clone + PartialEq + Eq;
+
     /// The chain extension for the environment.
     ///
     /// This is a type that is defined through the `#[ink::chain_extension]` procedural macro.
@@ -104,6 +171,15 @@ pub trait Environment {
     type ChainExtension;
 
     /// The fraction of the deposit costs that should be used as rent per block.
+    ///
+    /// # Note
+    ///
+    /// Modern `pallet-contracts`/`pallet-revive` runtimes no longer charge
+    /// rent; storage costs are instead accounted for by the storage-deposit
+    /// model (see [`StorageDeposit`]). This associated type only exists for
+    /// chains still running the legacy tombstone/rent mechanism and is
+    /// gated behind the `ink-legacy-rent` feature.
+    #[cfg(feature = "ink-legacy-rent")]
     type RentFraction: 'static + scale::Codec + Clone + PartialEq + Eq + Ord + PerThing;
 }
 
@@ -118,12 +194,68 @@ pub enum DefaultEnvironment {}
 impl Environment for DefaultEnvironment {
     const MAX_EVENT_TOPICS: usize = 4;
 
-    type AccountId = AccountId;
+    // Mirrors the limits of the default substrate `Schedule`; downstream
+    // runtimes should override these to match their own configuration.
+    const MAX_MEMORY_PAGES: u32 = 16;
+    const MAX_STACK_HEIGHT: u32 = 65_536;
+    const MAX_CALL_DEPTH: u32 = 32;
+
+    // The default substrate-native development chain does not assign itself a
+    // production chain id; downstream runtimes should override this constant
+    // with the `chain_id` their `pallet-revive`/`pallet-contracts` instance
+    // reports.
+    const CHAIN_ID: u64 = 0;
+
+    // The classic `pallet-contracts` storage API has no notion of transient
+    // storage; it was introduced later, by `pallet-revive`.
+    const SUPPORTS_TRANSIENT_STORAGE: bool = false;
+
+    type AccountId = AccountId32;
     type Balance = Balance;
     type Hash = Hash;
     type Timestamp = Timestamp;
     type BlockNumber = BlockNumber;
+    type Randomness = RandomnessOutput<Self>;
     type ChainExtension = NoChainExtension;
+    #[cfg(feature = "ink-legacy-rent")]
+    type RentFraction = RentFraction;
+}
+
+/// The fundamental types of an environment targeting EVM-compatible
+/// (pallet-revive) chains.
+///
+/// This mirrors [`DefaultEnvironment`] except for its `AccountId`, which is
+/// the 20-byte [`EthAccountId`] rather than the 32-byte [`AccountId32`] used
+/// by substrate-native `pallet-contracts` chains. Swapping a contract's
+/// `Environment` associated type between the two is enough to target either
+/// chain family with the same contract source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(TypeInfo))]
+pub enum EthEnvironment {}
+
+impl Environment for EthEnvironment {
+    const MAX_EVENT_TOPICS: usize = 4;
+
+    // As with `DefaultEnvironment`, downstream runtimes should override these
+    // to match their own `Schedule`.
+    const MAX_MEMORY_PAGES: u32 = 16;
+    const MAX_STACK_HEIGHT: u32 = 65_536;
+    const MAX_CALL_DEPTH: u32 = 32;
+
+    // As with `DefaultEnvironment`, downstream runtimes should override this
+    // with their actual `chain_id`.
+    const CHAIN_ID: u64 = 0;
+
+    const SUPPORTS_TRANSIENT_STORAGE: bool = true;
+
+    type AccountId = EthAccountId;
+    type Balance = Balance;
+    type Hash = Hash;
+    type Timestamp = Timestamp;
+    type BlockNumber = BlockNumber;
+    type Randomness = RandomnessOutput<Self>;
+    type ChainExtension = NoChainExtension;
+    #[cfg(feature = "ink-legacy-rent")]
     type RentFraction = RentFraction;
 }
 
@@ -139,12 +271,23 @@ pub type BlockNumber = u32;
 /// The default rent fraction type.
 pub type RentFraction = Perbill;
 
-/// The default environment `AccountId` type.
+/// The default environment `AccountId` type, generic over its width in bytes.
 ///
 /// # Note
 ///
-/// This is a mirror of the `AccountId` type used in the default configuration
-/// of PALLET contracts.
+/// Substrate-native `pallet-contracts` chains identify accounts with a 32-byte
+/// `AccountId` (`N` = 32, see the [`AccountId32`] type alias), while
+/// EVM-compatible `pallet-revive` chains identify accounts with a 20-byte
+/// `H160` address (`N` = 20, see [`EthAccountId`]). Keeping the width generic
+/// allows both kinds of chains to reuse the same `Clear`/`AsRef`/`AsMut`/
+/// `TryFrom` machinery through a single [`Environment::AccountId`].
+///
+/// # Note
+///
+/// `N` has no default: where nothing around it fixes the width, a bare
+/// `AccountId::default()` will not compile. Always go through [`Environment::AccountId`] (so `N`
+/// comes from the concrete `Environment`) or a fixed-width alias like
+/// [`AccountId32`]/[`EthAccountId`] rather than naming `AccountId<_>` bare.
 #[derive(
     Debug,
     Copy,
@@ -157,20 +300,94 @@ pub type RentFraction = Perbill;
     Encode,
     Decode,
     From,
-    Default,
 )]
 #[cfg_attr(feature = "std", derive(TypeInfo))]
-pub struct AccountId([u8; 32]);
+pub struct AccountId<const N: usize>([u8; N]);
 
-impl<'a> TryFrom<&'a [u8]> for AccountId {
+impl<const N: usize> AccountId<N> {
+    /// Returns the raw, fixed-size byte representation of the address.
+    pub fn as_bytes(&self) -> &[u8; N] {
+        &self.0
+    }
+}
+
+// `std`/`core` only provide `Default for [T; N]` for array lengths produced by
+// macro expansion, not generically over an arbitrary const `N`, so `Default`
+// is implemented by hand here instead of derived.
+impl<const N: usize> Default for AccountId<N> {
+    fn default() -> Self {
+        Self([0u8; N])
+    }
+}
+
+impl<'a, const N: usize> TryFrom<&'a [u8]> for AccountId<N> {
     type Error = TryFromSliceError;
 
     fn try_from(bytes: &'a [u8]) -> Result<Self, TryFromSliceError> {
-        let address = <[u8; 32]>::try_from(bytes)?;
+        let address = <[u8; N]>::try_from(bytes)?;
         Ok(Self(address))
     }
 }
 
+impl<const N: usize> AsRef<[u8]> for AccountId<N> {
+    fn as_ref(&self) -> &[u8] {
+        &self.0[..]
+    }
+}
+
+impl<const N: usize> AsMut<[u8]> for AccountId<N> {
+    fn as_mut(&mut self) -> &mut [u8] {
+        &mut self.0[..]
+    }
+}
+
+impl<const N: usize> Clear for AccountId<N> {
+    fn is_clear(&self) -> bool {
+        self.0.iter().all(|&byte| byte == 0x00)
+    }
+
+    fn clear() -> Self {
+        Self([0x00; N])
+    }
+}
+
+/// The default environment `AccountId` type used by substrate-native
+/// `pallet-contracts` chains.
+pub type AccountId32 = AccountId<32>;
+
+/// The `H160` address type used by EVM-compatible `pallet-revive` chains.
+pub type EthAccountId = AccountId<20>;
+
+impl AccountId<32> {
+    /// Derives the Ethereum-style 20-byte address for this 32-byte `AccountId`.
+    ///
+    /// The mapping keeps the trailing 20 bytes of the 32-byte id, mirroring
+    /// how pallet-revive derives an `H160` from a native account id (a full
+    /// keccak-reduction is left to runtimes that need collision resistance
+    /// across the whole 32-byte space).
+    pub fn to_eth_address(&self) -> EthAccountId {
+        let mut bytes = [0u8; 20];
+        bytes.copy_from_slice(&self.0[12..32]);
+        AccountId(bytes)
+    }
+}
+
+impl AccountId<20> {
+    /// Maps an Ethereum-style 20-byte address back to a deterministic, padded
+    /// 32-byte `AccountId`.
+    ///
+    /// The address is right-aligned and left-padded with zero bytes, so
+    /// [`AccountId::<32>::to_eth_address`] of the result returns this
+    /// address. The reverse round trip is not the identity: the leading 12
+    /// bytes of a 32-byte id are dropped by `to_eth_address` and come back
+    /// as zeros.
+    pub fn to_account_id(&self) -> AccountId<32> {
+        let mut bytes = [0u8; 32];
+        bytes[12..32].copy_from_slice(&self.0);
+        AccountId(bytes)
+    }
+}
+
 /// The default environment `Hash` type.
 ///
 /// # Note
@@ -246,7 +463,35 @@ impl Clear for Hash {
     }
 }
 
+/// The result of querying on-chain randomness: the random value together
+/// with the block number at which it was known to be determined.
+///
+/// # Note
+///
+/// `known_since` must be checked against the current block number before
+/// the random value is trusted: a value that became known too close to (or
+/// at) the current block can still be biased by whoever authors that
+/// block, so low-entropy early-block randomness should be rejected.
+#[derive(Clone, PartialEq, Eq, Encode, Decode)]
+#[cfg_attr(test, derive(Debug))]
+#[cfg_attr(feature = "std", derive(TypeInfo))]
+pub struct RandomnessOutput<T: Environment> {
+    /// The random value, domain-separated by hashing the caller-supplied
+    /// subject together with the runtime seed.
+    pub value: T::Hash,
+
+    /// The block number at which `value` was known to be determined.
+    pub known_since: T::BlockNumber,
+}
+
 /// Information needed for rent calculations that can be requested by a contract.
+///
+/// # Note
+///
+/// Superseded by the storage-deposit model (see [`StorageDeposit`]) on
+/// runtimes that no longer charge rent. Only kept for chains still running
+/// the legacy tombstone/rent mechanism, behind the `ink-legacy-rent` feature.
+#[cfg(feature = "ink-legacy-rent")]
 #[derive(scale::Decode)]
 #[cfg_attr(test, derive(Debug, PartialEq))]
 pub struct RentParams<T: Environment> {
@@ -324,6 +569,13 @@ pub struct RentParams<T: Environment> {
 ///
 /// The `current_*` fields do **not** consider changes to the code's `refcount`
 /// made during the currently running call.
+///
+/// # Note
+///
+/// Superseded by the storage-deposit model (see [`StorageDeposit`]) on
+/// runtimes that no longer charge rent. Only kept for chains still running
+/// the legacy tombstone/rent mechanism, behind the `ink-legacy-rent` feature.
+#[cfg(feature = "ink-legacy-rent")]
 #[derive(scale::Decode)]
 #[cfg_attr(test, derive(Debug, PartialEq))]
 pub struct RentStatus<T: Environment> {
@@ -347,4 +599,64 @@ pub struct RentStatus<T: Environment> {
 
     /// Reserved for backwards compatible changes to this data structure.
     pub _reserved: Option<()>,
+}
+
+/// The result of reserving or freeing storage on behalf of a contract.
+///
+/// Storing data reserves `Balance` from the depositor, which is refunded
+/// when the data is freed again; this replaces the rent mechanism
+/// (see [`RentParams`]/[`RentStatus`]) on runtimes running the
+/// storage-deposit model.
+///
+/// [`crate::call::CallBuilder`] and [`crate::call::InstantiateBuilder`]
+/// accept an optional `storage_deposit_limit(T::Balance)` that bounds how
+/// much of this deposit a single call or instantiation may charge; left
+/// unset, the encoded call carries `None`, meaning "no limit".
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(TypeInfo))]
+pub enum StorageDeposit<T: Environment> {
+    /// The transaction reduced storage consumption and the given amount of
+    /// balance is refunded to the depositor.
+    Refund(T::Balance),
+    /// The transaction increased storage consumption and the given amount of
+    /// balance is charged to the depositor.
+    Charge(T::Balance),
+}
+
+/// Information about the storage-deposit rates and reserves that can be
+/// requested by a contract.
+#[derive(scale::Decode)]
+#[cfg_attr(test, derive(Debug, PartialEq))]
+pub struct StorageDepositParams<T: Environment> {
+    /// The balance a contract needs to deposit per storage byte held, for as
+    /// long as that byte stays in storage.
+    pub deposit_per_byte: T::Balance,
+
+    /// The balance a contract needs to deposit per storage item held, for as
+    /// long as that item stays in storage.
+    pub deposit_per_item: T::Balance,
+
+    /// The balance currently reserved as this contract's storage deposit.
+    pub reserved_deposit: T::Balance,
+
+    /// Reserved for backwards compatible changes to this data structure.
+    pub _reserved: Option<()>,
+}
+
+/// A two-dimensional measure of execution weight, matching how modern
+/// substrate runtimes meter a contract call.
+///
+/// Returned by `ink_env::weight_left()` in place of a single `u64`, so that
+/// contracts doing adaptive batch sizing can query both the time and the
+/// proof-size budget that remains.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(TypeInfo))]
+pub struct Weight {
+    /// The weight of computation time used, e.g. for processing database reads
+    /// and writes, message passing, and other computational overhead.
+    pub ref_time: u64,
+
+    /// The weight of the proof size, i.e. how much space the validity proof
+    /// needed to justify this execution takes up in a block.
+    pub proof_size: u64,
 }
\ No newline at end of file